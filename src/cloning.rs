@@ -1,57 +1,145 @@
-use std::cell::UnsafeCell;
+pub use self::core::CloningCell;
+
 use std::rc::{Rc, Weak};
 
-/// A mutable memory location that clones its contents on retrieval.
-pub struct CloningCell<T: NonSelfReferentialClone>(UnsafeCell<T>);
+#[cfg(feature = "unsize")]
+use std::ops::CoerceUnsized;
 
-impl<T: NonSelfReferentialClone> CloningCell<T> {
-    /// Creates a new `CloningCell` containing the given value.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use mitochondria::CloningCell;
-    ///
-    /// let c = CloningCell::new("Hello cytosol!".to_owned());
-    /// ```
-    #[inline]
-    pub fn new(value: T) -> Self {
-        CloningCell(UnsafeCell::new(value))
+#[allow(unsafe_code)]
+mod core {
+    use std::cell::UnsafeCell;
+    use std::cmp::Ordering;
+    use std::hash::{Hash, Hasher};
+
+    /// A mutable memory location that clones its contents on retrieval.
+    pub struct CloningCell<T: super::NonSelfReferentialClone>(UnsafeCell<T>);
+
+    impl<T: super::NonSelfReferentialClone> CloningCell<T> {
+        /// Creates a new `CloningCell` containing the given value.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use mitochondria::CloningCell;
+        ///
+        /// let c = CloningCell::new("Hello cytosol!".to_owned());
+        /// ```
+        #[inline]
+        pub fn new(value: T) -> Self {
+            CloningCell(UnsafeCell::new(value))
+        }
+
+        /// Returns a clone of the contained value.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use mitochondria::CloningCell;
+        ///
+        /// let c = CloningCell::new("Hello lysosome!".to_owned());
+        ///
+        /// let greeting = c.get();
+        /// ```
+        #[inline]
+        pub fn get(&self) -> T {
+            unsafe { (*self.0.get()).clone() }
+        }
+
+        /// Sets the contained value.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use mitochondria::CloningCell;
+        ///
+        /// let c = CloningCell::new("Hello vacuole!".to_owned());
+        ///
+        /// c.set("Hello cytoskeleton!".to_owned());
+        /// ```
+        #[inline]
+        pub fn set(&self, value: T) {
+            unsafe { *self.0.get() = value; }
+        }
+    }
+
+    // `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` read the contained
+    // value through a shared reference instead of cloning it, so they're
+    // only meaningful in single-threaded use.
+    impl<T: super::NonSelfReferentialClone + PartialEq> PartialEq for CloningCell<T> {
+        fn eq(&self, other: &Self) -> bool {
+            unsafe { *self.0.get() == *other.0.get() }
+        }
+    }
+
+    impl<T: super::NonSelfReferentialClone + Eq> Eq for CloningCell<T> {}
+
+    impl<T: super::NonSelfReferentialClone + PartialOrd> PartialOrd for CloningCell<T> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            unsafe { (*self.0.get()).partial_cmp(&*other.0.get()) }
+        }
+    }
+
+    impl<T: super::NonSelfReferentialClone + Ord> Ord for CloningCell<T> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            unsafe { (*self.0.get()).cmp(&*other.0.get()) }
+        }
     }
 
-    /// Returns a clone of the contained value.
+    impl<T: super::NonSelfReferentialClone + Hash> Hash for CloningCell<T> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            unsafe { (*self.0.get()).hash(state) }
+        }
+    }
+}
+
+impl<T: NonSelfReferentialClone> CloningCell<T> {
+    /// Updates the contained value by cloning it, applying `f` to the
+    /// clone, and storing the result.
     ///
     /// # Example
     ///
     /// ```
     /// use mitochondria::CloningCell;
     ///
-    /// let c = CloningCell::new("Hello lysosome!".to_owned());
+    /// let c = CloningCell::new("Hello ".to_owned());
+    /// c.update(|mut s| { s += "ribosome!"; s });
     ///
-    /// let greeting = c.get();
+    /// assert_eq!(c.get(), "Hello ribosome!");
     /// ```
     #[inline]
-    pub fn get(&self) -> T {
-        unsafe { (*self.0.get()).clone() }
+    pub fn update<F: FnOnce(T) -> T>(&self, f: F) {
+        let value = self.get();
+        self.set(f(value));
     }
 
-    /// Sets the contained value.
+    /// Takes the value out of this cell, leaving `Default::default()` in
+    /// its place.
     ///
     /// # Example
     ///
     /// ```
     /// use mitochondria::CloningCell;
     ///
-    /// let c = CloningCell::new("Hello vacuole!".to_owned());
+    /// let c = CloningCell::new("Hello ribosome!".to_owned());
     ///
-    /// c.set("Hello cytoskeleton!".to_owned());
+    /// let greeting = c.take();
+    /// assert_eq!(greeting, "Hello ribosome!");
+    /// assert_eq!(c.get(), "");
     /// ```
     #[inline]
-    pub fn set(&self, value: T) {
-        unsafe { *self.0.get() = value; } 
+    pub fn take(&self) -> T where T: Default {
+        let value = self.get();
+        self.set(Default::default());
+        value
     }
 }
 
+#[cfg(feature = "unsize")]
+impl<T, U> CoerceUnsized<CloningCell<U>> for CloningCell<T>
+    where T: NonSelfReferentialClone + CoerceUnsized<U>,
+          U: NonSelfReferentialClone,
+{}
+
 /// A `Clone` implementation that will not access itself through reference
 /// cycles during cloning, which would introduce mutable aliasing.
 ///
@@ -79,12 +167,71 @@ impl<T: NonSelfReferentialClone> CloningCell<T> {
 /// rc.set(Some(Evil(Box::new(5), rc.clone()))); // Make a reference cycle.
 /// rc.get();
 /// ```
+#[allow(unsafe_code)]
 pub unsafe trait NonSelfReferentialClone: Clone {}
 
+#[allow(unsafe_code)]
 unsafe impl NonSelfReferentialClone for String {}
 
+#[allow(unsafe_code)]
 unsafe impl<T> NonSelfReferentialClone for Rc<T> {}
+#[allow(unsafe_code)]
 unsafe impl<T> NonSelfReferentialClone for Weak<T> {}
 
+#[allow(unsafe_code)]
 unsafe impl<T: NonSelfReferentialClone> NonSelfReferentialClone for Box<T> {}
+#[allow(unsafe_code)]
 unsafe impl<T: NonSelfReferentialClone> NonSelfReferentialClone for Option<T> {}
+
+#[cfg(test)]
+mod tests {
+    use CloningCell;
+
+    #[test]
+    fn smoketest() {
+        let c = CloningCell::new("ribosome".to_owned());
+        assert_eq!(c.get(), "ribosome");
+        c.set("nucleolus".to_owned());
+        assert_eq!(c.get(), "nucleolus");
+    }
+
+    #[test]
+    fn update() {
+        let c = CloningCell::new("ribosome".to_owned());
+        c.update(|mut s| { s += ", unite!"; s });
+        assert_eq!(c.get(), "ribosome, unite!");
+    }
+
+    #[test]
+    fn take() {
+        let c = CloningCell::new("ribosome".to_owned());
+        assert_eq!(c.take(), "ribosome");
+        assert_eq!(c.get(), "");
+    }
+
+    #[test]
+    fn eq() {
+        let a = CloningCell::new("ribosome".to_owned());
+        let b = CloningCell::new("ribosome".to_owned());
+        let c = CloningCell::new("nucleolus".to_owned());
+        assert!(a == b);
+        assert!(a != c);
+    }
+
+    #[test]
+    fn ord() {
+        let a = CloningCell::new("nucleolus".to_owned());
+        let b = CloningCell::new("ribosome".to_owned());
+        assert!(a < b);
+    }
+
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn hash() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(CloningCell::new("ribosome".to_owned()));
+        assert!(set.contains(&CloningCell::new("ribosome".to_owned())));
+    }
+}
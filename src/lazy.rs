@@ -0,0 +1,163 @@
+pub use self::core::Lazy;
+
+mod core {
+    use std::ops::{Deref, DerefMut};
+
+    use move_::MoveCell;
+    use once::OnceCell;
+
+    /// A value that is computed on first access and cached for later ones.
+    ///
+    /// `Lazy` pairs a `OnceCell<T>` with the closure that produces its
+    /// value, so callers don't have to hand-roll the closure-plumbing
+    /// every time a struct wants a field that's expensive to compute but
+    /// not always needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mitochondria::Lazy;
+    ///
+    /// let greeting = Lazy::new(|| "Hello ribosome!".to_owned());
+    ///
+    /// assert_eq!(&*greeting, "Hello ribosome!");
+    /// ```
+    pub struct Lazy<T, F = fn() -> T> {
+        cell: OnceCell<T>,
+        init: MoveCell<Option<F>>,
+    }
+
+    impl<T, F> Lazy<T, F> where F: FnOnce() -> T {
+        /// Creates a new `Lazy` that will call `init` to produce its value
+        /// the first time it is dereferenced.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::Lazy;
+        ///
+        /// let c = Lazy::new(|| "Hello cytosol!".to_owned());
+        /// ```
+        #[inline]
+        pub fn new(init: F) -> Self {
+            Lazy {
+                cell: OnceCell::new(),
+                init: MoveCell::new(Some(init)),
+            }
+        }
+
+        /// Forces evaluation of this `Lazy`'s value and returns a reference
+        /// to the result.
+        ///
+        /// If this is not the first call, the cached value from the first
+        /// call is returned and `init` is not called again.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::Lazy;
+        ///
+        /// let c = Lazy::new(|| "Hello lysosome!".to_owned());
+        /// assert_eq!(c.force(), "Hello lysosome!");
+        /// ```
+        #[inline]
+        pub fn force(&self) -> &T {
+            self.cell.init_once(|| {
+                let init = self.init.replace(None).unwrap();
+                init()
+            })
+        }
+
+        /// Consumes the `Lazy`, returning the computed value if it had
+        /// already been forced, or `Err(self)` unchanged otherwise.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::Lazy;
+        ///
+        /// let c = Lazy::new(|| "Hello vesicle!".to_owned());
+        /// let c = c.into_value().unwrap_err();
+        ///
+        /// c.force();
+        /// assert_eq!(c.into_value().ok(), Some("Hello vesicle!".to_owned()));
+        /// ```
+        pub fn into_value(self) -> Result<T, Self> {
+            match self.cell.as_ref() {
+                Some(_) => {
+                    let Lazy { cell, init } = self;
+                    drop(init);
+                    Ok(cell.into_inner().unwrap())
+                }
+                None => Err(self),
+            }
+        }
+    }
+
+    impl<T, F> Deref for Lazy<T, F> where F: FnOnce() -> T {
+        type Target = T;
+
+        #[inline]
+        fn deref(&self) -> &T {
+            self.force()
+        }
+    }
+
+    impl<T, F> DerefMut for Lazy<T, F> where F: FnOnce() -> T {
+        #[inline]
+        fn deref_mut(&mut self) -> &mut T {
+            self.force();
+            self.cell.as_mut().unwrap()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use Lazy;
+
+    #[test]
+    fn smoketest() {
+        let c = Lazy::new(|| "ribosome");
+        assert_eq!(c.force(), &"ribosome");
+        assert_eq!(*c, "ribosome");
+    }
+
+    #[test]
+    fn deref_mut() {
+        let mut c = Lazy::new(|| "ribosome".to_owned());
+        *c += ", unite!";
+        assert_eq!(&*c, "ribosome, unite!");
+    }
+
+    #[test]
+    fn called_once() {
+        use MoveCell;
+
+        let calls = MoveCell::new(0);
+        let c = Lazy::new(|| {
+            let n = calls.replace(0);
+            calls.replace(n + 1);
+            "ribosome"
+        });
+
+        assert_eq!(c.force(), &"ribosome");
+        assert_eq!(c.force(), &"ribosome");
+        assert_eq!(calls.replace(0), 1);
+    }
+
+    #[test]
+    fn into_value() {
+        let c = Lazy::new(|| "ribosome".to_owned());
+        let c = match c.into_value() {
+            Ok(_) => panic!("should not have been forced yet"),
+            Err(c) => c,
+        };
+
+        c.force();
+        match c.into_value() {
+            Ok(value) => assert_eq!(value, "ribosome"),
+            Err(_) => panic!("should have been forced"),
+        }
+    }
+}
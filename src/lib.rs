@@ -7,6 +7,7 @@
 #![deny(unsafe_code)]
 
 #![cfg_attr(feature = "no_std", no_std)]
+#![cfg_attr(feature = "unsize", feature(coerce_unsized))]
 
 #[cfg(feature = "no_std")]
 extern crate core as std;
@@ -14,6 +15,14 @@ extern crate core as std;
 #[path="move.rs"]
 mod move_;
 mod once;
+mod lazy;
+#[cfg(not(feature = "no_std"))]
+mod cloning;
+#[cfg(all(feature = "sync", not(feature = "no_std")))]
+pub mod sync;
 
 pub use move_::MoveCell;
 pub use once::OnceCell;
+pub use lazy::Lazy;
+#[cfg(not(feature = "no_std"))]
+pub use cloning::CloningCell;
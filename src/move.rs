@@ -1,9 +1,16 @@
 pub use self::core::MoveCell;
 
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
 #[allow(unsafe_code)]
 mod core {
     use std::cell::UnsafeCell;
     use std::mem;
+    use std::ptr;
+
+    #[cfg(not(feature = "no_std"))]
+    use std::process;
 
     /// A mutable memory location that steals ownership.
     pub struct MoveCell<T> {
@@ -94,6 +101,91 @@ mod core {
         pub fn as_mut(&mut self) -> &mut T {
             unsafe { &mut *self.value.get() }
         }
+
+        /// Takes the value out of this cell, leaving `Default::default()`
+        /// in its place.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::MoveCell;
+        ///
+        /// let c = MoveCell::new("Hello peroxisome!".to_owned());
+        ///
+        /// let greeting = c.take();
+        /// assert_eq!(greeting, "Hello peroxisome!");
+        /// assert_eq!(c.into_inner(), "");
+        /// ```
+        #[inline]
+        pub fn take(&self) -> T where T: Default {
+            self.replace(Default::default())
+        }
+
+        /// Swaps the values of two `MoveCell`s.
+        ///
+        /// If `self` and `other` refer to the same cell, this is a no-op:
+        /// swapping a value with itself would otherwise require holding two
+        /// mutable references to the same memory at once.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::MoveCell;
+        ///
+        /// let a = MoveCell::new("Hello".to_owned());
+        /// let b = MoveCell::new("Goodbye".to_owned());
+        ///
+        /// a.swap(&b);
+        ///
+        /// assert_eq!(a.into_inner(), "Goodbye");
+        /// assert_eq!(b.into_inner(), "Hello");
+        /// ```
+        pub fn swap(&self, other: &MoveCell<T>) {
+            if ptr::eq(self, other) {
+                return;
+            }
+            unsafe {
+                ptr::swap(self.value.get(), other.value.get());
+            }
+        }
+
+        /// Updates the contained value by applying `f` to it.
+        ///
+        /// If `f` panics, the process aborts (with the `no_std` feature,
+        /// where `abort` is unavailable, it loops forever instead). While
+        /// `f` runs, this cell's backing storage holds a bitwise copy of the old value that has
+        /// already been moved into `f`; unwinding past that point would let
+        /// safe code reach that storage and drop the old value a second
+        /// time, so there is no sound way to let the panic propagate.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::MoveCell;
+        ///
+        /// let c = MoveCell::new("Porins,".to_owned());
+        /// c.update(|mut s| { s += " unite!"; s });
+        ///
+        /// assert_eq!(c.into_inner(), "Porins, unite!");
+        /// ```
+        pub fn update<F>(&self, f: F) where F: FnOnce(T) -> T {
+            struct AbortOnDrop;
+
+            impl Drop for AbortOnDrop {
+                fn drop(&mut self) {
+                    #[cfg(not(feature = "no_std"))]
+                    { process::abort(); }
+                    #[cfg(feature = "no_std")]
+                    { loop {} }
+                }
+            }
+
+            let guard = AbortOnDrop;
+            let slot = self.value.get();
+            let new = f(unsafe { ptr::read(slot) });
+            mem::forget(guard);
+            unsafe { ptr::write(slot, new); }
+        }
     }
 }
 
@@ -111,6 +203,46 @@ impl<T> From<T> for MoveCell<T> {
     }
 }
 
+// `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` all read the contained
+// value through a shared reference obtained from `as_ptr`. This is only
+// meaningful in single-threaded use, since another thread could be
+// concurrently replacing the value being read.
+#[allow(unsafe_code)]
+impl<T: PartialEq> PartialEq for MoveCell<T> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { *self.as_ptr() == *other.as_ptr() }
+    }
+}
+
+impl<T: Eq> Eq for MoveCell<T> {}
+
+#[allow(unsafe_code)]
+impl<T: PartialOrd> PartialOrd for MoveCell<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        unsafe { (*self.as_ptr()).partial_cmp(&*other.as_ptr()) }
+    }
+}
+
+#[allow(unsafe_code)]
+impl<T: Ord> Ord for MoveCell<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        unsafe { (*self.as_ptr()).cmp(&*other.as_ptr()) }
+    }
+}
+
+#[allow(unsafe_code)]
+impl<T: Hash> Hash for MoveCell<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe { (*self.as_ptr()).hash(state) }
+    }
+}
+
+#[cfg(feature = "unsize")]
+use std::ops::CoerceUnsized;
+
+#[cfg(feature = "unsize")]
+impl<T: CoerceUnsized<U>, U> CoerceUnsized<MoveCell<U>> for MoveCell<T> {}
+
 #[cfg(test)]
 mod tests {
     use MoveCell;
@@ -155,4 +287,56 @@ mod tests {
         fn assert_send<T: Send>() {}
         assert_send::<MoveCell<usize>>();
     }
+
+    #[test]
+    fn take() {
+        let x = MoveCell::new("ribosome".to_owned());
+        assert_eq!(x.take(), "ribosome");
+        assert_eq!(x.into_inner(), "");
+    }
+
+    #[test]
+    fn swap() {
+        let a = MoveCell::new("ribosome");
+        let b = MoveCell::new("nucleolus");
+        a.swap(&b);
+        assert_eq!(a.into_inner(), "nucleolus");
+        assert_eq!(b.into_inner(), "ribosome");
+    }
+
+    #[test]
+    fn swap_with_self() {
+        let a = MoveCell::new("ribosome");
+        a.swap(&a);
+        assert_eq!(a.into_inner(), "ribosome");
+    }
+
+    #[test]
+    fn update() {
+        let x = MoveCell::new("ribosome".to_owned());
+        x.update(|mut s| { s += ", unite!"; s });
+        assert_eq!(x.into_inner(), "ribosome, unite!");
+    }
+
+    #[test]
+    fn eq() {
+        assert!(MoveCell::new("ribosome") == MoveCell::new("ribosome"));
+        assert!(MoveCell::new("ribosome") != MoveCell::new("nucleolus"));
+    }
+
+    #[test]
+    fn ord() {
+        assert!(MoveCell::new(1) < MoveCell::new(2));
+        assert_eq!(MoveCell::new(1).cmp(&MoveCell::new(1)), ::std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn hash() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(MoveCell::new("ribosome"));
+        assert!(set.contains(&MoveCell::new("ribosome")));
+    }
 }
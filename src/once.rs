@@ -1,6 +1,8 @@
 pub use self::core::OnceCell;
 
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 #[allow(unsafe_code)]
 mod core {
@@ -195,6 +197,78 @@ mod core {
         pub fn as_mut(&mut self) -> Option<&mut T> {
             unsafe { (*self.0.get()).as_mut() }
         }
+
+        /// Consumes the `OnceCell`, returning the wrapped value if it was
+        /// initialised.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::OnceCell;
+        ///
+        /// let c = OnceCell::<String>::new();
+        /// assert_eq!(c.into_inner(), None);
+        ///
+        /// let c = OnceCell::new_with_value("Hello nucleus!".to_owned());
+        /// assert_eq!(c.into_inner(), Some("Hello nucleus!".to_owned()));
+        /// ```
+        #[inline]
+        pub fn into_inner(self) -> Option<T> {
+            self.0.into_inner()
+        }
+
+        /// Resets the cell back to uninitialised, returning the value it
+        /// held if it was initialised.
+        ///
+        /// This call borrows `OnceCell` mutably (at compile-time) which
+        /// guarantees that we possess the only reference, so the reset is
+        /// sound even though the cell may be reused afterwards.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::OnceCell;
+        ///
+        /// let mut c = OnceCell::new();
+        /// c.init_once(|| "ribosome".to_owned());
+        ///
+        /// assert_eq!(c.take(), Some("ribosome".to_owned()));
+        /// assert_eq!(c.as_ref(), None);
+        /// ```
+        #[inline]
+        pub fn take(&mut self) -> Option<T> {
+            self.0.get_mut().take()
+        }
+
+        /// Returns a mutable reference to the value, initializing it with
+        /// `f` first if the cell is empty.
+        ///
+        /// This call borrows `OnceCell` mutably (at compile-time), so it
+        /// can initialize and reinitialize the cell across the phases of a
+        /// computation without needing interior mutability to do it.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::OnceCell;
+        ///
+        /// let mut c = OnceCell::new();
+        ///
+        /// *c.get_mut_or_init(|| "Nucleo".to_owned()) += "lus!";
+        /// assert_eq!(c.as_ref().unwrap(), "Nucleolus!");
+        ///
+        /// // Once initialised, `f` is not called again.
+        /// assert_eq!(c.get_mut_or_init(|| "Goodbye!".to_owned()), "Nucleolus!");
+        /// ```
+        #[inline]
+        pub fn get_mut_or_init<F>(&mut self, f: F) -> &mut T
+            where F: FnOnce() -> T
+        {
+            if self.0.get_mut().is_none() {
+                *self.0.get_mut() = Some(f());
+            }
+            self.0.get_mut().as_mut().unwrap()
+        }
     }
 }
 
@@ -260,6 +334,40 @@ impl<T> From<T> for OnceCell<T> {
     }
 }
 
+// Comparing and hashing `OnceCell`s compares and hashes `Option<&T>`, so two
+// uninitialised cells are equal and an uninitialised cell sorts before an
+// initialised one.
+impl<T: PartialEq> PartialEq for OnceCell<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<T: Eq> Eq for OnceCell<T> {}
+
+impl<T: PartialOrd> PartialOrd for OnceCell<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_ref().partial_cmp(&other.as_ref())
+    }
+}
+
+impl<T: Ord> Ord for OnceCell<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(&other.as_ref())
+    }
+}
+
+impl<T: Hash> Hash for OnceCell<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state)
+    }
+}
+
+// `OnceCell<T>` stores its payload as `UnsafeCell<Option<T>>`, and `Option`
+// does not implement `CoerceUnsized`, so there is no way to coerce
+// `OnceCell<T>` to `OnceCell<U>` without re-laying out the cell. Unlike
+// `MoveCell`/`CloningCell`, this cell does not support unsized coercion.
+
 #[cfg(test)]
 mod tests {
     use OnceCell;
@@ -328,4 +436,60 @@ mod tests {
         fn assert_send<T: Send>() {}
         assert_send::<OnceCell<usize>>();
     }
+
+    #[test]
+    fn into_inner() {
+        let x = OnceCell::new();
+        assert_eq!(x.into_inner(), None::<&str>);
+
+        let y = OnceCell::new_with_value("ribosome");
+        assert_eq!(y.into_inner(), Some("ribosome"));
+    }
+
+    #[test]
+    fn take() {
+        let mut x = OnceCell::new();
+        assert_eq!(x.take(), None);
+
+        x.init_once(|| "ribosome");
+        assert_eq!(x.take(), Some("ribosome"));
+        assert_eq!(x.as_ref(), None);
+
+        x.init_once(|| "nucleolus");
+        assert_eq!(x.as_ref(), Some(&"nucleolus"));
+    }
+
+    #[test]
+    fn get_mut_or_init() {
+        let mut x = OnceCell::new();
+        assert_eq!(x.get_mut_or_init(|| "ribosome".to_owned()), "ribosome");
+        assert_eq!(x.get_mut_or_init(|| "nucleolus".to_owned()), "ribosome");
+
+        x.take();
+        assert_eq!(x.get_mut_or_init(|| "nucleolus".to_owned()), "nucleolus");
+    }
+
+    #[test]
+    fn eq() {
+        assert_eq!(OnceCell::<&str>::new(), OnceCell::new());
+        assert_eq!(OnceCell::from("ribosome"), OnceCell::from("ribosome"));
+        assert!(OnceCell::from("ribosome") != OnceCell::new());
+        assert!(OnceCell::from("ribosome") != OnceCell::from("nucleolus"));
+    }
+
+    #[test]
+    fn ord() {
+        assert!(OnceCell::<&str>::new() < OnceCell::from("ribosome"));
+        assert!(OnceCell::from("nucleolus") < OnceCell::from("ribosome"));
+    }
+
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn hash() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(OnceCell::from("ribosome"));
+        assert!(set.contains(&OnceCell::from("ribosome")));
+    }
 }
@@ -0,0 +1,376 @@
+//! Thread-safe variants of mitochondria's cell types.
+//!
+//! This module requires the `sync` feature and is unavailable under
+//! `no_std`, since it relies on blocking other threads while a value is
+//! being initialized.
+
+pub use self::core::OnceCell;
+
+use std::fmt;
+
+#[allow(unsafe_code)]
+mod core {
+    use std::cell::UnsafeCell;
+    use std::hint;
+    use std::mem;
+    use std::sync::atomic::{AtomicU8, Ordering};
+
+    const INCOMPLETE: u8 = 0;
+    const RUNNING: u8 = 1;
+    const COMPLETE: u8 = 2;
+
+    /// A thread-safe mutable memory location that can be set only once.
+    ///
+    /// This is the `Sync` counterpart to
+    /// [`mitochondria::OnceCell`](../struct.OnceCell.html), which is `Send`
+    /// but not `Sync`. Unlike that cell, this one is safe to share between
+    /// threads: concurrent calls to `try_init_once` race to become the
+    /// initializer, and every other caller blocks until a value is ready.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use mitochondria::sync::OnceCell;
+    ///
+    /// let c = OnceCell::new();
+    ///
+    /// assert_eq!(c.as_ref(), None);
+    ///
+    /// let value = c.init_once(|| "ribosome");
+    ///
+    /// assert_eq!(value, &"ribosome");
+    /// assert_eq!(c.as_ref(), Some(&"ribosome"));
+    /// ```
+    pub struct OnceCell<T> {
+        state: AtomicU8,
+        value: UnsafeCell<Option<T>>,
+    }
+
+    unsafe impl<T> Send for OnceCell<T> where T: Send {}
+    unsafe impl<T> Sync for OnceCell<T> where T: Send + Sync {}
+
+    impl<T> OnceCell<T> {
+        /// Creates a new `OnceCell`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::sync::OnceCell;
+        ///
+        /// let c = OnceCell::<String>::new();
+        /// ```
+        #[inline]
+        pub fn new() -> Self {
+            OnceCell {
+                state: AtomicU8::new(INCOMPLETE),
+                value: UnsafeCell::new(None),
+            }
+        }
+
+        /// Creates a new `OnceCell` initialised with `value`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::sync::OnceCell;
+        ///
+        /// let c = OnceCell::new_with_value(Some("Hello vesicle!".to_owned()));
+        /// ```
+        #[inline]
+        pub fn new_with_value(value: T) -> Self {
+            OnceCell {
+                state: AtomicU8::new(COMPLETE),
+                value: UnsafeCell::new(Some(value)),
+            }
+        }
+
+        /// Calls a function to try to initialize this cell.
+        ///
+        /// If the cell was already initialized (by this thread or another
+        /// one), the function is *not* called. If another thread is
+        /// currently initializing the cell, this call blocks until that
+        /// thread finishes.
+        ///
+        /// This method returns `Err(error)` if `f` was called and returned
+        /// an error, or `Ok(&value)` otherwise.
+        ///
+        /// If `f` panics, or returns an error, the cell is left
+        /// uninitialized so a later call (from any thread) may try again.
+        ///
+        /// # Reentrancy
+        ///
+        /// Unlike [`mitochondria::OnceCell`](../struct.OnceCell.html),
+        /// which supports calling `init_once`/`try_init_once` again from
+        /// inside `f`, this cell has no way to distinguish a reentrant call
+        /// from one arriving on another thread: both see the state stuck
+        /// at `RUNNING` and spin. Calling `init_once` or `try_init_once` on
+        /// the same cell from inside `f` deadlocks.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::sync::OnceCell;
+        ///
+        /// let c = OnceCell::new();
+        ///
+        /// assert_eq!(c.try_init_once(|| Err(())), Err(()));
+        ///
+        /// let greeting = c.try_init_once::<(), _>(|| {
+        ///     Ok("Hello ribosome!".to_owned())
+        /// }).unwrap();
+        /// ```
+        pub fn try_init_once<E, F>(&self, f: F) -> Result<&T, E>
+            where F: FnOnce() -> Result<T, E>
+        {
+            loop {
+                match self.state.compare_exchange(
+                    INCOMPLETE, RUNNING, Ordering::Acquire, Ordering::Acquire)
+                {
+                    Ok(_) => return self.init(f),
+                    Err(COMPLETE) => return Ok(self.get().unwrap()),
+                    Err(RUNNING) => {
+                        while self.state.load(Ordering::Acquire) == RUNNING {
+                            hint::spin_loop();
+                        }
+                        // Either the initializer finished (state is now
+                        // `COMPLETE`) or it backed out (state is back to
+                        // `INCOMPLETE`); loop around and re-check.
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
+
+        /// Runs `f`, having just won the race to initialize this cell.
+        fn init<E, F>(&self, f: F) -> Result<&T, E>
+            where F: FnOnce() -> Result<T, E>
+        {
+            // If `f` panics, this guard's `Drop` runs during unwinding and
+            // resets the state to `INCOMPLETE` so another thread can retry.
+            // A normal return (`Ok` or `Err`) forgets the guard and sets
+            // the final state explicitly instead.
+            struct ResetOnUnwind<'a>(&'a AtomicU8);
+
+            impl<'a> Drop for ResetOnUnwind<'a> {
+                fn drop(&mut self) {
+                    self.0.store(INCOMPLETE, Ordering::Release);
+                }
+            }
+
+            let guard = ResetOnUnwind(&self.state);
+            let result = f();
+            mem::forget(guard);
+
+            match result {
+                Ok(value) => {
+                    unsafe { *self.value.get() = Some(value); }
+                    self.state.store(COMPLETE, Ordering::Release);
+                    Ok(self.get().unwrap())
+                }
+                Err(error) => {
+                    self.state.store(INCOMPLETE, Ordering::Release);
+                    Err(error)
+                }
+            }
+        }
+
+        /// Returns `None` if the cell is not initialised, or else returns a
+        /// reference to the value wrapped in `Some`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::sync::OnceCell;
+        ///
+        /// let c = OnceCell::new();
+        ///
+        /// assert!(c.as_ref().is_none());
+        ///
+        /// let greeting = c.init_once(|| "Hello nucleus!".to_owned());
+        /// assert_eq!(c.as_ref(), Some(greeting));
+        /// ```
+        #[inline]
+        pub fn as_ref(&self) -> Option<&T> {
+            self.get()
+        }
+
+        /// Returns `None` if the cell is not initialised, or else returns a
+        /// mutable reference to the value wrapped in `Some`.
+        ///
+        /// This call borrows `OnceCell` mutably (at compile-time) which
+        /// guarantees that we possess the only reference.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use mitochondria::sync::OnceCell;
+        ///
+        /// let mut c = OnceCell::new();
+        ///
+        /// assert!(c.as_mut().is_none());
+        ///
+        /// c.init_once(|| "Nucleo".to_owned());
+        /// *c.as_mut().unwrap() += "lus!";
+        /// assert_eq!(c.as_ref().unwrap(), "Nucleolus!");
+        /// ```
+        #[inline]
+        pub fn as_mut(&mut self) -> Option<&mut T> {
+            unsafe { (*self.value.get()).as_mut() }
+        }
+
+        #[inline]
+        fn get(&self) -> Option<&T> {
+            if self.state.load(Ordering::Acquire) == COMPLETE {
+                unsafe { (*self.value.get()).as_ref() }
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl<T> OnceCell<T> {
+    /// Calls a function to initialize this cell and borrows its value.
+    ///
+    /// If the cell was already initialised, the function is *not* called
+    /// and the returned value is the one that was already there. If
+    /// another thread is currently initializing the cell, this call blocks
+    /// until that thread finishes.
+    ///
+    /// Calling `init_once` on the same cell from inside `f` deadlocks; see
+    /// [`try_init_once`](#method.try_init_once) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mitochondria::sync::OnceCell;
+    ///
+    /// let c = OnceCell::new();
+    ///
+    /// let greeting: &str = c.init_once(|| "Hello ribosome!".to_owned());
+    /// ```
+    #[inline]
+    pub fn init_once<F>(&self, f: F) -> &T where F: FnOnce() -> T {
+        self.try_init_once(|| Ok::<T, ()>(f())).unwrap()
+    }
+}
+
+impl<T: Clone> Clone for OnceCell<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.as_ref()
+            .cloned()
+            .map(OnceCell::new_with_value)
+            .unwrap_or_else(OnceCell::new)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("OnceCell").field(&self.as_ref()).finish()
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    #[inline]
+    fn default() -> Self {
+        OnceCell::new()
+    }
+}
+
+impl<T> From<T> for OnceCell<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        OnceCell::new_with_value(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sync::OnceCell;
+
+    #[test]
+    fn smoketest() {
+        let x = OnceCell::new();
+        assert_eq!(x.as_ref(), None);
+        assert_eq!(x.init_once(|| "ribosome"), &"ribosome");
+        assert_eq!(x.as_ref(), Some(&"ribosome"));
+        assert_eq!(x.init_once(|| "nucleolus"), &"ribosome");
+        assert_eq!(x.as_ref(), Some(&"ribosome"));
+
+        let y = OnceCell::new();
+        assert_eq!(y.try_init_once(|| Err(())), Err(()));
+        assert_eq!(y.as_ref(), None);
+        assert_eq!(
+            y.try_init_once::<(), _>(|| Ok("ribosome")),
+            Ok(&"ribosome"));
+        assert_eq!(y.as_ref(), Some(&"ribosome"));
+
+        let z = OnceCell::new_with_value("ribosome");
+        assert_eq!(z.as_ref(), Some(&"ribosome"));
+    }
+
+    #[test]
+    fn as_mut() {
+        let mut x = OnceCell::new();
+        assert!(x.as_mut().is_none());
+        x.init_once(|| "Nucleo".to_owned());
+        *x.as_mut().unwrap() += "lus!";
+        assert_eq!(x.as_ref().unwrap(), "Nucleolus!");
+    }
+
+    #[test]
+    fn clone() {
+        let x = OnceCell::new();
+        assert_eq!(x.clone().as_ref(), None);
+        x.init_once(|| "ribosome");
+        assert_eq!(x.clone().as_ref(), Some(&"ribosome"));
+    }
+
+    #[test]
+    fn debug() {
+        let x = OnceCell::new();
+        assert_eq!(format!("{:?}", x), "OnceCell(None)");
+        x.init_once(|| "ribosome");
+        assert_eq!(format!("{:?}", x), "OnceCell(Some(\"ribosome\"))");
+    }
+
+    #[test]
+    fn default() {
+        let x = OnceCell::<usize>::default();
+        assert_eq!(x.as_ref(), None);
+    }
+
+    #[test]
+    fn from() {
+        let x = OnceCell::from("ribosome");
+        assert_eq!(x.as_ref(), Some(&"ribosome"));
+    }
+
+    #[test]
+    fn send_and_sync() {
+        fn assert_send<T: Send>() {}
+        fn assert_sync<T: Sync>() {}
+        assert_send::<OnceCell<usize>>();
+        assert_sync::<OnceCell<usize>>();
+    }
+
+    #[test]
+    fn race_across_threads() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let cell = Arc::new(OnceCell::new());
+        let handles: Vec<_> = (0..8).map(|i| {
+            let cell = cell.clone();
+            thread::spawn(move || *cell.init_once(|| i))
+        }).collect();
+
+        let results: Vec<usize> = handles.into_iter()
+            .map(|h| h.join().unwrap())
+            .collect();
+
+        // Every thread must observe the same winning value.
+        assert!(results.iter().all(|&v| v == results[0]));
+    }
+}